@@ -0,0 +1,137 @@
+use swiftsc_frontend::ast::{Block, Statement, StatementKind};
+
+/// A straight-line run of statements with no internal branches.
+///
+/// `stmts` only ever holds the statement itself for `If`/`While`/`For`
+/// (their condition/bounds, not the nested bodies) since the bodies are
+/// lowered into their own blocks and linked in via `successors`.
+#[derive(Debug, Default)]
+pub(crate) struct BasicBlock<'a> {
+    pub stmts: Vec<&'a Statement>,
+    pub successors: Vec<usize>,
+}
+
+/// A control-flow graph lowered from a function body, cut at `if`/`while`/
+/// `for` boundaries so dataflow facts can be propagated along branch and
+/// loop back-edges instead of walked linearly.
+#[derive(Debug, Default)]
+pub(crate) struct ControlFlowGraph<'a> {
+    pub blocks: Vec<BasicBlock<'a>>,
+}
+
+impl<'a> ControlFlowGraph<'a> {
+    pub fn build(body: &'a Block) -> Self {
+        let mut cfg = ControlFlowGraph {
+            blocks: vec![BasicBlock::default()],
+        };
+        cfg.lower_block(body, 0);
+        cfg
+    }
+
+    fn new_block(&mut self) -> usize {
+        self.blocks.push(BasicBlock::default());
+        self.blocks.len() - 1
+    }
+
+    /// Lowers `block` into the graph starting at `current`, returning the
+    /// index of the block execution falls through to afterwards, or `None`
+    /// if every path through `block` ends in `return` — a genuine CFG exit,
+    /// not a fallthrough, so callers must not wire a successor edge out of
+    /// it.
+    fn lower_block(&mut self, block: &'a Block, mut current: usize) -> Option<usize> {
+        for stmt in &block.stmts {
+            match &stmt.kind {
+                StatementKind::Return(_) => {
+                    // Anything lexically after a `return` in the same block
+                    // is unreachable; stop lowering and report no fallthrough.
+                    self.blocks[current].stmts.push(stmt);
+                    return None;
+                }
+                StatementKind::If {
+                    then_branch,
+                    else_branch,
+                    ..
+                } => {
+                    self.blocks[current].stmts.push(stmt);
+
+                    let then_entry = self.new_block();
+                    self.blocks[current].successors.push(then_entry);
+                    let then_exit = self.lower_block(then_branch, then_entry);
+
+                    let join = self.new_block();
+                    let mut falls_through = then_exit.is_some();
+                    if let Some(then_exit) = then_exit {
+                        self.blocks[then_exit].successors.push(join);
+                    }
+
+                    if let Some(else_branch) = else_branch {
+                        let else_entry = self.new_block();
+                        self.blocks[current].successors.push(else_entry);
+                        let else_exit = self.lower_block(else_branch, else_entry);
+                        if let Some(else_exit) = else_exit {
+                            self.blocks[else_exit].successors.push(join);
+                            falls_through = true;
+                        }
+                    } else {
+                        self.blocks[current].successors.push(join);
+                        falls_through = true;
+                    }
+
+                    if !falls_through {
+                        // Both arms return, so nothing after the `if` is
+                        // reachable.
+                        return None;
+                    }
+                    current = join;
+                }
+                StatementKind::While { body, .. } => {
+                    let header = self.new_block();
+                    self.blocks[current].successors.push(header);
+                    self.blocks[header].stmts.push(stmt);
+
+                    let body_entry = self.new_block();
+                    self.blocks[header].successors.push(body_entry);
+                    let body_exit = self.lower_block(body, body_entry);
+                    if let Some(body_exit) = body_exit {
+                        self.blocks[body_exit].successors.push(header);
+                    }
+
+                    let after = self.new_block();
+                    self.blocks[header].successors.push(after);
+                    current = after;
+                }
+                StatementKind::For { body, .. } => {
+                    let header = self.new_block();
+                    self.blocks[current].successors.push(header);
+                    self.blocks[header].stmts.push(stmt);
+
+                    let body_entry = self.new_block();
+                    self.blocks[header].successors.push(body_entry);
+                    let body_exit = self.lower_block(body, body_entry);
+                    if let Some(body_exit) = body_exit {
+                        self.blocks[body_exit].successors.push(header);
+                    }
+
+                    let after = self.new_block();
+                    self.blocks[header].successors.push(after);
+                    current = after;
+                }
+                _ => {
+                    self.blocks[current].stmts.push(stmt);
+                }
+            }
+        }
+        Some(current)
+    }
+
+    /// Predecessors of each block, derived from `successors`.
+    pub fn predecessors(&self) -> Vec<Vec<usize>> {
+        let mut preds = vec![Vec::new(); self.blocks.len()];
+        for (i, block) in self.blocks.iter().enumerate() {
+            for &succ in &block.successors {
+                preds[succ].push(i);
+            }
+        }
+        preds
+    }
+}
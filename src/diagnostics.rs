@@ -0,0 +1,119 @@
+use swiftsc_frontend::ast::Span;
+
+/// A single labeled source location within a [`Diagnostic`].
+#[derive(Debug, Clone)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+
+/// A diagnostic with one primary location and any number of secondary,
+/// labeled locations — e.g. the external call that tainted a later state
+/// write. Mirrors the "declared here / data flows here" style of
+/// multi-span compiler diagnostics.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub primary: Label,
+    pub secondary: Vec<Label>,
+}
+
+impl Diagnostic {
+    pub fn new(span: Span, message: impl Into<String>) -> Self {
+        Diagnostic {
+            primary: Label {
+                span,
+                message: message.into(),
+            },
+            secondary: Vec::new(),
+        }
+    }
+
+    pub fn with_secondary(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.secondary.push(Label {
+            span,
+            message: message.into(),
+        });
+        self
+    }
+}
+
+/// Renders a diagnostic against the original source: each labeled span gets
+/// its source line printed with a caret underline and the label text beside
+/// it, primary first followed by any secondary/related spans.
+pub fn render(diagnostic: &Diagnostic, source: &str) -> String {
+    let mut out = String::new();
+    render_label(&mut out, source, &diagnostic.primary);
+    for label in &diagnostic.secondary {
+        render_label(&mut out, source, label);
+    }
+    out
+}
+
+/// Resolves a byte offset into the source to just its 1-based line number.
+pub(crate) fn line_of(source: &str, offset: usize) -> usize {
+    line_col(source, offset).0
+}
+
+fn render_label(out: &mut String, source: &str, label: &Label) {
+    let (line, col) = line_col(source, label.span.start);
+    let text = source.lines().nth(line.saturating_sub(1)).unwrap_or("");
+    let underline_len = label.span.end.saturating_sub(label.span.start).max(1);
+
+    out.push_str(&format!("{:>4} | {}\n", line, text));
+    out.push_str(&format!(
+        "     | {}{} {}\n",
+        " ".repeat(col.saturating_sub(1)),
+        "^".repeat(underline_len),
+        label.message
+    ));
+}
+
+/// Resolves a byte offset into the source to a 1-based (line, column).
+pub(crate) fn line_col(source: &str, offset: usize) -> (usize, usize) {
+    let mut line = 1;
+    let mut col = 1;
+    for (i, ch) in source.char_indices() {
+        if i >= offset {
+            break;
+        }
+        if ch == '\n' {
+            line += 1;
+            col = 1;
+        } else {
+            col += 1;
+        }
+    }
+    (line, col)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_col_resolves_offsets_on_later_lines() {
+        let source = "let a = 1;\nlet b = 2;\nlet c = 3;";
+        assert_eq!(line_col(source, 0), (1, 1));
+        assert_eq!(line_col(source, 11), (2, 1));
+        assert_eq!(line_col(source, 15), (2, 5));
+    }
+
+    #[test]
+    fn line_col_does_not_resolve_to_the_wrong_line() {
+        let source = "let a = 1;\nlet b = 2;";
+        assert_ne!(line_col(source, 15).0, 1);
+    }
+
+    #[test]
+    fn render_includes_primary_and_secondary_labels() {
+        let source = "let a = 1;\nlet b = a;";
+        let diagnostic = Diagnostic::new(Span::new(19, 20), "`a` read here")
+            .with_secondary(Span::new(4, 5), "`a` declared here");
+
+        let rendered = render(&diagnostic, source);
+        assert!(rendered.contains("`a` read here"));
+        assert!(rendered.contains("`a` declared here"));
+        assert!(rendered.contains("let b = a;"));
+        assert!(rendered.contains("let a = 1;"));
+    }
+}
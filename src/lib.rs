@@ -1,5 +1,12 @@
+mod cfg;
+pub mod diagnostics;
+pub mod policy;
+pub mod report;
 pub mod security;
 
+pub use diagnostics::Diagnostic;
+pub use policy::{SecurityPolicy, Severity};
+pub use report::Report;
 pub use security::{SecurityAnalyzer, SecurityWarning};
 
 /// Analyzer version
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// How a retained finding for a given rule code should be treated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Fails the build — surfaced via `SecurityAnalyzer::has_critical_warnings`.
+    Error,
+    Warning,
+    Info,
+    /// Rule is disabled entirely; matching findings are dropped.
+    Allow,
+}
+
+impl Severity {
+    fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "error" => Some(Severity::Error),
+            "warning" => Some(Severity::Warning),
+            "info" => Some(Severity::Info),
+            "allow" => Some(Severity::Allow),
+            _ => None,
+        }
+    }
+}
+
+/// Maps each rule code (`SEC-002`, `SEC-003`, ...) to the [`Severity`] a
+/// retained finding for that rule should carry.
+#[derive(Debug, Clone)]
+pub struct SecurityPolicy {
+    severities: HashMap<String, Severity>,
+}
+
+impl Default for SecurityPolicy {
+    fn default() -> Self {
+        let mut severities = HashMap::new();
+        severities.insert("SEC-002".to_string(), Severity::Warning);
+        severities.insert("SEC-003".to_string(), Severity::Warning);
+        severities.insert("SEC-004".to_string(), Severity::Warning);
+        SecurityPolicy { severities }
+    }
+}
+
+impl SecurityPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn severity(&self, code: &str) -> Severity {
+        self.severities.get(code).copied().unwrap_or(Severity::Warning)
+    }
+
+    pub fn set_severity(&mut self, code: impl Into<String>, severity: Severity) {
+        self.severities.insert(code.into(), severity);
+    }
+
+    pub fn load(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        contents.parse().map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+impl FromStr for SecurityPolicy {
+    type Err = String;
+
+    /// Parses a policy file: one `RULE = severity` assignment per line,
+    /// blank lines and `#`-comments ignored, e.g.:
+    ///
+    /// ```text
+    /// SEC-002 = error
+    /// SEC-004 = allow
+    /// ```
+    fn from_str(contents: &str) -> Result<Self, Self::Err> {
+        let mut policy = SecurityPolicy::default();
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (code, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {}: expected `RULE = severity`", i + 1))?;
+            let severity = Severity::parse(value.trim())
+                .ok_or_else(|| format!("line {}: unknown severity `{}`", i + 1, value.trim()))?;
+            policy.set_severity(code.trim(), severity);
+        }
+        Ok(policy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rule_assignments_and_ignores_comments_and_blanks() {
+        let contents = "\n# a comment\nSEC-002 = error\nSEC-004 = allow\n";
+        let policy: SecurityPolicy = contents.parse().expect("should parse");
+
+        assert_eq!(policy.severity("SEC-002"), Severity::Error);
+        assert_eq!(policy.severity("SEC-004"), Severity::Allow);
+        // Untouched rule keeps its default.
+        assert_eq!(policy.severity("SEC-003"), Severity::Warning);
+    }
+
+    #[test]
+    fn rejects_a_line_with_an_unknown_severity() {
+        let result: Result<SecurityPolicy, _> = "SEC-002 = maybe".parse();
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,216 @@
+use crate::diagnostics;
+use crate::policy::{SecurityPolicy, Severity};
+use crate::security::SecurityWarning;
+
+/// Metadata describing a rule itself, independent of any particular finding.
+#[derive(Debug, Clone, Copy)]
+pub struct RuleMeta {
+    pub code: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+/// Looks up the short name and description shown alongside a rule code in
+/// a report. Unknown codes get a placeholder rather than panicking, since
+/// reports are meant to be generated from whatever warnings exist.
+pub fn rule_metadata(code: &str) -> RuleMeta {
+    match code {
+        "SEC-002" => RuleMeta {
+            code: "SEC-002",
+            name: "potential-reentrancy",
+            description: "State is written after an external call may have happened on the same path.",
+        },
+        "SEC-003" => RuleMeta {
+            code: "SEC-003",
+            name: "unchecked-arithmetic",
+            description: "Arithmetic operation is not guarded by checked/safe math.",
+        },
+        "SEC-004" => RuleMeta {
+            code: "SEC-004",
+            name: "uninitialized-storage",
+            description: "A storage field is not definitely initialized by the constructor, or a field or local is read before it is definitely assigned.",
+        },
+        _ => RuleMeta {
+            code: "SEC-000",
+            name: "unknown",
+            description: "Unknown rule.",
+        },
+    }
+}
+
+/// A span resolved to 1-based line/column, suitable for JSON/SARIF output.
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+pub struct ResolvedSpan {
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl ResolvedSpan {
+    fn resolve(source: &str, span: swiftsc_frontend::ast::Span) -> Self {
+        let (start_line, start_column) = diagnostics::line_col(source, span.start);
+        let (end_line, end_column) = diagnostics::line_col(source, span.end);
+        ResolvedSpan {
+            start_line,
+            start_column,
+            end_line,
+            end_column,
+        }
+    }
+}
+
+/// One finding in stable, serializable form: rule code, message, resolved
+/// severity, and the primary span resolved to line/column.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Finding {
+    pub rule: String,
+    pub message: String,
+    pub severity: String,
+    pub span: ResolvedSpan,
+}
+
+/// A full analyzer run, ready to be written out as JSON or SARIF for CI to
+/// consume instead of scraping `SecurityWarning::message` text.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct Report {
+    pub findings: Vec<Finding>,
+}
+
+impl Report {
+    pub fn build(warnings: &[SecurityWarning], policy: &SecurityPolicy, source: &str) -> Self {
+        let findings = warnings
+            .iter()
+            .map(|warning| Finding {
+                rule: warning.code().to_string(),
+                message: warning.message(),
+                severity: severity_name(policy.severity(warning.code())).to_string(),
+                span: ResolvedSpan::resolve(source, warning.diagnostic().primary.span),
+            })
+            .collect();
+        Report { findings }
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Renders the run as a SARIF 2.1.0 log, the format CI dashboards and
+    /// code-review tools ingest for static-analysis results.
+    pub fn to_sarif(&self) -> serde_json::Value {
+        let mut rule_codes: Vec<&str> = self.findings.iter().map(|f| f.rule.as_str()).collect();
+        rule_codes.sort_unstable();
+        rule_codes.dedup();
+
+        let rules: Vec<_> = rule_codes
+            .into_iter()
+            .map(|code| {
+                let meta = rule_metadata(code);
+                serde_json::json!({
+                    "id": meta.code,
+                    "name": meta.name,
+                    "shortDescription": { "text": meta.description },
+                })
+            })
+            .collect();
+
+        let results: Vec<_> = self
+            .findings
+            .iter()
+            .map(|finding| {
+                serde_json::json!({
+                    "ruleId": finding.rule,
+                    "level": sarif_level(&finding.severity),
+                    "message": { "text": finding.message },
+                    "locations": [{
+                        "physicalLocation": {
+                            "region": {
+                                "startLine": finding.span.start_line,
+                                "startColumn": finding.span.start_column,
+                                "endLine": finding.span.end_line,
+                                "endColumn": finding.span.end_column,
+                            }
+                        }
+                    }],
+                })
+            })
+            .collect();
+
+        serde_json::json!({
+            "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": { "driver": { "name": "swiftsc-analyzer", "rules": rules } },
+                "results": results,
+            }],
+        })
+    }
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "info",
+        Severity::Allow => "none",
+    }
+}
+
+fn sarif_level(severity: &str) -> &'static str {
+    match severity {
+        "error" => "error",
+        "warning" => "warning",
+        _ => "note",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swiftsc_frontend::ast::Span;
+
+    fn sample_warning() -> SecurityWarning {
+        SecurityWarning::UncheckedArithmetic {
+            operation: "Add".to_string(),
+            span: Span::new(4, 5),
+        }
+    }
+
+    #[test]
+    fn build_resolves_span_and_severity_from_policy() {
+        let mut policy = SecurityPolicy::new();
+        policy.set_severity("SEC-003", Severity::Error);
+        let source = "let a = b\n  + c;";
+
+        let report = Report::build(&[sample_warning()], &policy, source);
+
+        assert_eq!(report.findings.len(), 1);
+        assert_eq!(report.findings[0].rule, "SEC-003");
+        assert_eq!(report.findings[0].severity, "error");
+        assert_eq!(report.findings[0].span.start_line, 1);
+    }
+
+    #[test]
+    fn to_json_round_trips_through_serde() {
+        let policy = SecurityPolicy::new();
+        let report = Report::build(&[sample_warning()], &policy, "a + b");
+
+        let json = report.to_json().expect("should serialize");
+        assert!(json.contains("SEC-003"));
+
+        let parsed: serde_json::Value = serde_json::from_str(&json).expect("should parse back");
+        assert_eq!(parsed["findings"][0]["rule"], "SEC-003");
+    }
+
+    #[test]
+    fn to_sarif_omits_unseen_rules_from_the_rules_list() {
+        let policy = SecurityPolicy::new();
+        let report = Report::build(&[sample_warning()], &policy, "a + b");
+
+        let sarif = report.to_sarif();
+        let rules = sarif["runs"][0]["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0]["id"], "SEC-003");
+        assert!(!rules.iter().any(|r| r["id"] == "SEC-004"));
+    }
+}
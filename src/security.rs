@@ -1,11 +1,28 @@
+use crate::cfg::ControlFlowGraph;
+use crate::diagnostics::{self, Diagnostic};
+use crate::policy::{SecurityPolicy, Severity};
 use swiftsc_frontend::ast::*;
+use std::collections::HashSet;
 
 #[derive(Debug, Clone)]
 pub enum SecurityWarning {
     PotentialOverflow { operation: String, span: Span },
-    UninitializedVariable { name: String, span: Span },
+    UninitializedVariable {
+        name: String,
+        span: Span,
+        /// Where the storage field itself was declared.
+        field_span: Span,
+    },
     UncheckedArithmetic { operation: String, span: Span },
-    PotentialReentrancy { message: String, span: Span },
+    PotentialReentrancy {
+        message: String,
+        span: Span,
+        /// Where the external call that tainted this write happened.
+        call_span: Option<Span>,
+    },
+    /// A read of `self.field` or a local that is not definitely assigned
+    /// on every path reaching it.
+    UseBeforeInit { name: String, span: Span },
 }
 
 impl SecurityWarning {
@@ -15,6 +32,7 @@ impl SecurityWarning {
             SecurityWarning::UninitializedVariable { .. } => "SEC-004",
             SecurityWarning::UncheckedArithmetic { .. } => "SEC-003",
             SecurityWarning::PotentialReentrancy { .. } => "SEC-002",
+            SecurityWarning::UseBeforeInit { .. } => "SEC-004",
         }
     }
 
@@ -32,14 +50,50 @@ impl SecurityWarning {
             SecurityWarning::PotentialReentrancy { message, .. } => {
                 format!("[{}] Potential Reentrancy: {}", self.code(), message)
             }
+            SecurityWarning::UseBeforeInit { name, .. } => {
+                format!("[{}] Use of possibly-uninitialized `{}`", self.code(), name)
+            }
+        }
+    }
+
+    /// The rich, multi-span form of this warning, suitable for rendering
+    /// against the original source via [`crate::diagnostics::render`].
+    pub fn diagnostic(&self) -> Diagnostic {
+        match self {
+            SecurityWarning::PotentialOverflow { operation, span } => {
+                Diagnostic::new(*span, format!("potential overflow in `{}`", operation))
+            }
+            SecurityWarning::UninitializedVariable { name, span, field_span } => {
+                Diagnostic::new(*span, format!("{} is never initialized here", name))
+                    .with_secondary(*field_span, "storage field declared here")
+            }
+            SecurityWarning::UncheckedArithmetic { operation, span } => Diagnostic::new(
+                *span,
+                format!("unchecked `{}` — consider using SafeMath", operation),
+            ),
+            SecurityWarning::PotentialReentrancy { message, span, call_span } => {
+                let diagnostic = Diagnostic::new(*span, format!("... but {}", message));
+                match call_span {
+                    Some(call_span) => diagnostic.with_secondary(*call_span, "external call happens here ..."),
+                    None => diagnostic,
+                }
+            }
+            SecurityWarning::UseBeforeInit { name, span } => {
+                Diagnostic::new(*span, format!("`{}` is read here before it is definitely assigned", name))
+            }
         }
     }
 }
 
 pub struct SecurityAnalyzer {
     warnings: Vec<SecurityWarning>,
-    external_call_seen: bool,
     current_function: Option<String>,
+    policy: SecurityPolicy,
+    /// Source text, used to resolve `// swiftsc-allow SEC-xxx` suppression
+    /// comments against a warning's primary span. Suppression is a no-op
+    /// without it.
+    source: Option<String>,
+    has_critical: bool,
 }
 
 impl Default for SecurityAnalyzer {
@@ -52,196 +106,465 @@ impl SecurityAnalyzer {
     pub fn new() -> Self {
         SecurityAnalyzer {
             warnings: Vec::new(),
-            external_call_seen: false,
             current_function: None,
+            policy: SecurityPolicy::default(),
+            source: None,
+            has_critical: false,
+        }
+    }
+
+    pub fn with_policy(policy: SecurityPolicy) -> Self {
+        SecurityAnalyzer {
+            policy,
+            ..Self::new()
         }
     }
 
+    /// Attaches the original source so inline suppression comments can be
+    /// resolved against warning spans. Call before `analyze_program`.
+    pub fn set_source(&mut self, source: impl Into<String>) {
+        self.source = Some(source.into());
+    }
+
+    /// Records a finding after consulting the policy: dropped entirely if
+    /// the rule is `Allow`ed or suppressed by an inline comment, otherwise
+    /// kept and, if it resolved to `Severity::Error`, latched into
+    /// `has_critical_warnings`.
+    fn push_warning(&mut self, warning: SecurityWarning) {
+        let severity = self.policy.severity(warning.code());
+        if severity == Severity::Allow || self.is_suppressed(&warning) {
+            return;
+        }
+        if severity == Severity::Error {
+            self.has_critical = true;
+        }
+        self.warnings.push(warning);
+    }
+
+    fn is_suppressed(&self, warning: &SecurityWarning) -> bool {
+        let Some(source) = &self.source else {
+            return false;
+        };
+        let line = diagnostics::line_of(source, warning.diagnostic().primary.span.start);
+        suppressed_lines(source, warning.code()).contains(&line)
+    }
+
     pub fn analyze_program(&mut self, program: &Program) {
         for item in &program.items {
             match item {
                 Item::Contract(contract) => self.analyze_contract(contract),
-                Item::Function(func) => self.analyze_function(func),
+                Item::Function(func) => {
+                    let initial = Self::param_names(func);
+                    self.analyze_function(func, &std::collections::HashMap::new(), initial)
+                }
                 _ => {}
             }
         }
     }
 
     fn analyze_contract(&mut self, contract: &Contract) {
-        let mut storage_fields = std::collections::HashSet::new();
+        let mut storage_fields = std::collections::HashMap::new();
         for member in &contract.members {
             if let ContractMember::Storage(fields) = member {
                 for field in fields {
-                    storage_fields.insert(field.name.clone());
+                    storage_fields.insert(field.name.clone(), field.span);
                 }
             }
         }
+        let storage_keys: HashSet<String> = storage_fields.keys().map(|f| Self::storage_key(f)).collect();
 
         for member in &contract.members {
             if let ContractMember::Init(func) = member {
-                let mut initialized = std::collections::HashSet::new();
-                self.collect_initializations(&func.body, &mut initialized);
-
-                for field in &storage_fields {
-                    if !initialized.contains(field) {
-                        // For constructor, span is the function body or the field itself if we had it.
-                        // Using func's body span for now.
-                        self.warnings.push(SecurityWarning::UninitializedVariable {
+                self.current_function = Some(func.name.clone());
+                // The constructor is the one place storage fields are NOT
+                // yet assumed initialized on entry — that's exactly what
+                // `check_storage_at_exit` verifies.
+                self.analyze_definite_assignment(func, &Self::param_names(func), &storage_fields, true);
+                self.current_function = None;
+            } else if let ContractMember::Function(func) = member {
+                let initial = Self::param_names(func).into_iter().chain(storage_keys.iter().cloned()).collect();
+                self.analyze_function(func, &storage_fields, initial);
+            }
+        }
+    }
+
+    fn analyze_function(&mut self, func: &Function, storage_fields: &std::collections::HashMap<String, Span>, initial: HashSet<String>) {
+        self.current_function = Some(func.name.clone());
+        self.analyze_reentrancy(&func.body);
+        self.analyze_definite_assignment(func, &initial, storage_fields, false);
+        self.analyze_block(&func.body);
+        self.current_function = None;
+    }
+
+    /// Names already guaranteed initialized on entry to `func`: its
+    /// parameters are bound by the time the body starts executing.
+    fn param_names(func: &Function) -> HashSet<String> {
+        func.params.iter().map(|p| p.name.clone()).collect()
+    }
+
+    /// Definite-assignment dataflow over the function's control-flow graph:
+    /// the *must-be-initialized* set at a block's entry is the
+    /// intersection of its predecessors' exit sets (so a name only counts
+    /// as initialized if every path reaching the block set it), and each
+    /// `self.field = ...` / `let x = ...` adds to the set flowing out of
+    /// the block. `initial` seeds the entry set of the function's first
+    /// block — parameters for every function, plus already-initialized
+    /// storage fields for everything except the constructor. Flags (1) any
+    /// read of `self.field` or a local not yet in the must-initialized
+    /// set, and, when `check_storage_at_exit` is set (constructors only),
+    /// (2) any storage field not in the set at every reachable exit block.
+    fn analyze_definite_assignment(
+        &mut self,
+        func: &Function,
+        initial: &HashSet<String>,
+        storage_fields: &std::collections::HashMap<String, Span>,
+        check_storage_at_exit: bool,
+    ) {
+        let body = &func.body;
+        let cfg = ControlFlowGraph::build(body);
+        let preds = cfg.predecessors();
+        let n = cfg.blocks.len();
+
+        let mut universe = initial.clone();
+        Self::collect_assigned_names(body, &mut universe);
+
+        let mut entry: Vec<HashSet<String>> = vec![universe.clone(); n];
+        entry[0] = initial.clone();
+        let mut exit: Vec<HashSet<String>> = (0..n)
+            .map(|b| Self::transfer_definite_assignment(&cfg.blocks[b], &entry[b]))
+            .collect();
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for b in 1..n {
+                if preds[b].is_empty() {
+                    continue;
+                }
+                let mut new_entry: Option<HashSet<String>> = None;
+                for &p in &preds[b] {
+                    new_entry = Some(match new_entry {
+                        None => exit[p].clone(),
+                        Some(acc) => acc.intersection(&exit[p]).cloned().collect(),
+                    });
+                }
+                let new_entry = new_entry.unwrap_or_default();
+                if new_entry != entry[b] {
+                    entry[b] = new_entry;
+                    changed = true;
+                }
+
+                let new_exit = Self::transfer_definite_assignment(&cfg.blocks[b], &entry[b]);
+                if new_exit != exit[b] {
+                    exit[b] = new_exit;
+                    changed = true;
+                }
+            }
+        }
+
+        for (b, block) in cfg.blocks.iter().enumerate() {
+            let mut set = entry[b].clone();
+            for stmt in &block.stmts {
+                self.check_statement_definite_assignment(stmt, &mut set);
+            }
+        }
+
+        if check_storage_at_exit {
+            for b in 0..n {
+                if !cfg.blocks[b].successors.is_empty() {
+                    continue;
+                }
+                for (field, field_span) in storage_fields {
+                    if !exit[b].contains(&Self::storage_key(field)) {
+                        self.push_warning(SecurityWarning::UninitializedVariable {
                             name: format!("Storage field '{}'", field),
-                            span: func.body.stmts.first().map(|s| s.span).unwrap_or(Span::new(1, 1)),
+                            span: cfg.blocks[b]
+                                .stmts
+                                .last()
+                                .copied()
+                                .or(body.stmts.last())
+                                .map(|s| s.span)
+                                .unwrap_or(Span::new(1, 1)),
+                            field_span: *field_span,
                         });
                     }
                 }
-            } else if let ContractMember::Function(func) = member {
-                self.analyze_function(func);
             }
         }
     }
 
-    fn collect_initializations(
-        &self,
-        block: &Block,
-        initialized: &mut std::collections::HashSet<String>,
-    ) {
+    fn storage_key(field: &str) -> String {
+        format!("self::{}", field)
+    }
+
+    /// Every name a `let x = ...` or `self.field = ...` could add to the
+    /// must-initialized set anywhere in `block`, recursing into nested
+    /// bodies. Used only to seed the dataflow's starting sets; transfer
+    /// functions still operate per-basic-block.
+    fn collect_assigned_names(block: &Block, names: &mut HashSet<String>) {
         for stmt in &block.stmts {
-            if let StatementKind::Expr(expr) = &stmt.kind {
-                if let ExpressionKind::Binary { left, op, .. } = &expr.kind {
-                    if *op == BinaryOp::Assign {
-                        if let ExpressionKind::FieldAccess { expr: obj, field } = &left.kind {
-                            if let ExpressionKind::Identifier(name) = &obj.kind {
-                                if name == "self" {
-                                    initialized.insert(field.clone());
-                                }
-                            }
-                        }
+            match &stmt.kind {
+                StatementKind::Let { name, .. } => {
+                    names.insert(name.clone());
+                }
+                StatementKind::Expr(expr) => Self::collect_assigned_name(expr, names),
+                StatementKind::If { then_branch, else_branch, .. } => {
+                    Self::collect_assigned_names(then_branch, names);
+                    if let Some(else_branch) = else_branch {
+                        Self::collect_assigned_names(else_branch, names);
                     }
                 }
+                StatementKind::While { body, .. } => Self::collect_assigned_names(body, names),
+                StatementKind::For { var, body, .. } => {
+                    names.insert(var.clone());
+                    Self::collect_assigned_names(body, names);
+                }
+                _ => {}
             }
         }
     }
 
-    fn analyze_function(&mut self, func: &Function) {
-        self.external_call_seen = false;
-        self.current_function = Some(func.name.clone());
-        self.analyze_block(&func.body);
-        self.current_function = None;
+    fn collect_assigned_name(expr: &Expression, names: &mut HashSet<String>) {
+        if let ExpressionKind::Binary { left, op, .. } = &expr.kind {
+            if *op == BinaryOp::Assign {
+                if let ExpressionKind::FieldAccess { expr: obj, field } = &left.kind {
+                    if let ExpressionKind::Identifier(name) = &obj.kind {
+                        if name == "self" {
+                            names.insert(Self::storage_key(field));
+                        }
+                    }
+                }
+            }
+        }
     }
 
-    fn analyze_block(&mut self, block: &Block) {
+    fn transfer_definite_assignment(block: &crate::cfg::BasicBlock, input: &HashSet<String>) -> HashSet<String> {
+        let mut set = input.clone();
         for stmt in &block.stmts {
-            self.analyze_statement(stmt);
+            match &stmt.kind {
+                StatementKind::Let { name, .. } => {
+                    set.insert(name.clone());
+                }
+                StatementKind::Expr(expr) => Self::collect_assigned_name(expr, &mut set),
+                // The loop header block holds the `For` statement itself
+                // (see `cfg::ControlFlowGraph`); the induction variable is
+                // bound for the body block that flows from this one.
+                StatementKind::For { var, .. } => {
+                    set.insert(var.clone());
+                }
+                _ => {}
+            }
         }
+        set
     }
 
-    fn analyze_statement(&mut self, stmt: &Statement) {
+    /// Mirrors `transfer_definite_assignment`'s statement handling, but
+    /// also checks every read against `set` (in order, so a read before
+    /// the statement that initializes it is still flagged) and emits
+    /// `UseBeforeInit` for anything not yet covered.
+    fn check_statement_definite_assignment(&mut self, stmt: &Statement, set: &mut HashSet<String>) {
         match &stmt.kind {
-            StatementKind::Let { init, .. } => {
-                self.analyze_expression(init);
+            StatementKind::Let { name, init, .. } => {
+                self.check_reads(init, set);
+                set.insert(name.clone());
             }
-            StatementKind::Expr(expr) => {
-                self.analyze_expression(expr);
+            StatementKind::Expr(expr) => self.check_reads(expr, set),
+            StatementKind::Return(Some(expr)) => self.check_reads(expr, set),
+            StatementKind::If { condition, .. } => self.check_reads(condition, set),
+            StatementKind::While { condition, .. } => self.check_reads(condition, set),
+            StatementKind::For { var, start, end, .. } => {
+                self.check_reads(start, set);
+                self.check_reads(end, set);
+                set.insert(var.clone());
             }
-            StatementKind::Return(Some(expr)) => {
-                self.analyze_expression(expr);
+            _ => {}
+        }
+    }
+
+    fn check_reads(&mut self, expr: &Expression, set: &mut HashSet<String>) {
+        match &expr.kind {
+            ExpressionKind::Binary { left, op, right } if *op == BinaryOp::Assign => {
+                if let Some(field) = self_field(left) {
+                    self.check_reads(right, set);
+                    set.insert(Self::storage_key(field));
+                    return;
+                }
+                self.check_reads(left, set);
+                self.check_reads(right, set);
             }
-            StatementKind::If {
-                condition,
-                then_branch,
-                else_branch,
-            } => {
-                self.analyze_expression(condition);
-                self.analyze_block(then_branch);
-                if let Some(eb) = else_branch {
-                    self.analyze_block(eb);
+            ExpressionKind::Identifier(name) => {
+                if name != "self" && !set.contains(name) {
+                    self.push_warning(SecurityWarning::UseBeforeInit {
+                        name: name.clone(),
+                        span: expr.span,
+                    });
                 }
             }
-            StatementKind::While { condition, body } => {
-                self.analyze_expression(condition);
-                self.analyze_block(body);
+            ExpressionKind::FieldAccess { .. } => {
+                let Some(field) = self_field(expr) else {
+                    return for_each_child_expr(expr, |child| self.check_reads(child, set));
+                };
+                let key = Self::storage_key(field);
+                if !set.contains(&key) {
+                    self.push_warning(SecurityWarning::UseBeforeInit {
+                        name: format!("self.{}", field),
+                        span: expr.span,
+                    });
+                }
             }
-            StatementKind::For { start, end, body, .. } => {
-                self.analyze_expression(start);
-                self.analyze_expression(end);
-                self.analyze_block(body);
+            ExpressionKind::Call { args, .. } => {
+                // The callee (a free function or `self`/`obj` method name)
+                // is a call target, not a value read — don't flag it.
+                for arg in args {
+                    self.check_reads(arg, set);
+                }
             }
-            _ => {}
+            _ => for_each_child_expr(expr, |child| self.check_reads(child, set)),
         }
     }
 
-    fn analyze_expression(&mut self, expr: &Expression) {
-        match &expr.kind {
-            ExpressionKind::Binary { left, op, right } => {
-                if *op == BinaryOp::Assign && self.external_call_seen {
-                    if let ExpressionKind::FieldAccess { expr: obj, .. } = &left.kind {
-                        if let ExpressionKind::Identifier(name) = &obj.kind {
-                            if name == "self" {
-                                self.warnings.push(SecurityWarning::PotentialReentrancy {
-                                    message: "Detected state modification after potential external call".to_string(),
-                                    span: expr.span,
-                                });
-                            }
-                        }
-                    }
-                }
+    /// Forward "may have made an external call on some path reaching here"
+    /// dataflow over the function's control-flow graph: a block's entry
+    /// fact is the union (logical OR) of its predecessors' exit facts and
+    /// its exit fact is the entry fact OR'd with whether the block itself
+    /// contains an external call, iterated to a fixed point so loop
+    /// back-edges propagate the fact around the loop body.
+    fn analyze_reentrancy(&mut self, body: &Block) {
+        let cfg = ControlFlowGraph::build(body);
+        let preds = cfg.predecessors();
+        let n = cfg.blocks.len();
 
-                match op {
-                    BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul => {
-                        let is_safe_context = self.current_function.as_ref().map_or(false, |name| {
-                            name.starts_with("checked_") || name.starts_with("safe_")
-                        });
+        let mut entry_fact = vec![false; n];
+        let mut entry_span: Vec<Option<Span>> = vec![None; n];
+        let mut exit_fact = vec![false; n];
+        let mut exit_span: Vec<Option<Span>> = vec![None; n];
 
-                        if !is_safe_context {
-                            self.warnings.push(SecurityWarning::UncheckedArithmetic {
-                                operation: format!("{:?}", op),
-                                span: expr.span,
-                            });
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for b in 0..n {
+                let mut new_entry = false;
+                let mut new_entry_span = None;
+                for &p in &preds[b] {
+                    if exit_fact[p] {
+                        new_entry = true;
+                        if new_entry_span.is_none() {
+                            new_entry_span = exit_span[p];
                         }
                     }
-                    _ => {}
                 }
-                self.analyze_expression(left);
-                self.analyze_expression(right);
-            }
-            ExpressionKind::Call { func, args, .. } => {
-                if let ExpressionKind::FieldAccess { expr: obj, .. } = &func.kind {
-                    if let ExpressionKind::Identifier(name) = &obj.kind {
-                        if name != "self" {
-                            self.external_call_seen = true;
-                        }
-                    }
+                if new_entry && !entry_fact[b] {
+                    entry_fact[b] = true;
+                    entry_span[b] = new_entry_span;
+                    changed = true;
                 }
 
-                self.analyze_expression(func);
-                for arg in args {
-                    self.analyze_expression(arg);
+                let mut local_call_seen = false;
+                let mut local_call_span = None;
+                for stmt in &cfg.blocks[b].stmts {
+                    Self::scan_statement_for_call(stmt, &mut local_call_seen, &mut local_call_span);
+                }
+
+                let new_exit = entry_fact[b] || local_call_seen;
+                if new_exit && !exit_fact[b] {
+                    exit_fact[b] = true;
+                    exit_span[b] = if local_call_seen { local_call_span } else { entry_span[b] };
+                    changed = true;
                 }
             }
-            ExpressionKind::FieldAccess { expr: obj, .. } => {
-                self.analyze_expression(obj);
-            }
-            ExpressionKind::Index { expr: obj, index } => {
-                self.analyze_expression(obj);
-                self.analyze_expression(index);
+        }
+
+        for (b, block) in cfg.blocks.iter().enumerate() {
+            let mut call_seen = entry_fact[b];
+            let mut call_span = entry_span[b];
+            for stmt in &block.stmts {
+                self.scan_statement_for_reentrancy(stmt, &mut call_seen, &mut call_span);
             }
-            ExpressionKind::Match { value, arms } => {
-                self.analyze_expression(value);
-                for arm in arms {
-                    self.analyze_expression(&arm.body);
-                }
+        }
+    }
+
+    /// Scans just the statement's own expressions (not nested bodies, which
+    /// the CFG already splits into their own blocks) for an external call,
+    /// without emitting any warnings.
+    fn scan_statement_for_call(stmt: &Statement, call_seen: &mut bool, call_span: &mut Option<Span>) {
+        for_each_stmt_expr(stmt, |expr| Self::scan_expr_for_call(expr, call_seen, call_span));
+    }
+
+    fn scan_expr_for_call(expr: &Expression, call_seen: &mut bool, call_span: &mut Option<Span>) {
+        if let ExpressionKind::Call { func, .. } = &expr.kind {
+            if is_external_call_target(func) {
+                *call_seen = true;
+                *call_span = Some(expr.span);
             }
-            ExpressionKind::StructInit { fields, .. } => {
-                for (_, f_expr) in fields {
-                    self.analyze_expression(f_expr);
+        }
+        for_each_child_expr(expr, |child| Self::scan_expr_for_call(child, call_seen, call_span));
+    }
+
+    /// Same traversal as `scan_statement_for_call`, but also emits
+    /// `PotentialReentrancy` for a `self.field = ...` write reached while
+    /// `call_seen` is true.
+    fn scan_statement_for_reentrancy(&mut self, stmt: &Statement, call_seen: &mut bool, call_span: &mut Option<Span>) {
+        for_each_stmt_expr(stmt, |expr| self.scan_expr_for_reentrancy(expr, call_seen, call_span));
+    }
+
+    fn scan_expr_for_reentrancy(&mut self, expr: &Expression, call_seen: &mut bool, call_span: &mut Option<Span>) {
+        match &expr.kind {
+            ExpressionKind::Binary { left, op, .. } if *op == BinaryOp::Assign && *call_seen => {
+                if self_field(left).is_some() {
+                    self.push_warning(SecurityWarning::PotentialReentrancy {
+                        message: "Detected state modification after potential external call".to_string(),
+                        span: expr.span,
+                        call_span: *call_span,
+                    });
                 }
             }
-            ExpressionKind::Try(e) => {
-                self.analyze_expression(e);
+            ExpressionKind::Call { func, .. } if is_external_call_target(func) => {
+                *call_seen = true;
+                *call_span = Some(expr.span);
             }
-            ExpressionKind::GenericInst { target, .. } => {
-                self.analyze_expression(target);
+            _ => {}
+        }
+        for_each_child_expr(expr, |child| self.scan_expr_for_reentrancy(child, call_seen, call_span));
+    }
+
+    fn analyze_block(&mut self, block: &Block) {
+        for stmt in &block.stmts {
+            self.analyze_statement(stmt);
+        }
+    }
+
+    fn analyze_statement(&mut self, stmt: &Statement) {
+        match &stmt.kind {
+            StatementKind::If { then_branch, else_branch, .. } => {
+                self.analyze_block(then_branch);
+                if let Some(eb) = else_branch {
+                    self.analyze_block(eb);
+                }
             }
+            StatementKind::While { body, .. } => self.analyze_block(body),
+            StatementKind::For { body, .. } => self.analyze_block(body),
             _ => {}
         }
+        for_each_stmt_expr(stmt, |expr| self.analyze_expression(expr));
+    }
+
+    fn analyze_expression(&mut self, expr: &Expression) {
+        if let ExpressionKind::Binary { op, .. } = &expr.kind {
+            if matches!(op, BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul) {
+                let is_safe_context = self
+                    .current_function
+                    .as_ref()
+                    .map_or(false, |name| name.starts_with("checked_") || name.starts_with("safe_"));
+
+                if !is_safe_context {
+                    self.push_warning(SecurityWarning::UncheckedArithmetic {
+                        operation: format!("{:?}", op),
+                        span: expr.span,
+                    });
+                }
+            }
+        }
+        for_each_child_expr(expr, |child| self.analyze_expression(child));
     }
 
     pub fn get_warnings(&self) -> &[SecurityWarning] {
@@ -249,6 +572,287 @@ impl SecurityAnalyzer {
     }
 
     pub fn has_critical_warnings(&self) -> bool {
-        false
+        self.has_critical
+    }
+
+    /// Builds a serializable [`crate::report::Report`] of the retained
+    /// warnings, resolving each one's span to line/column against `source`.
+    pub fn report(&self, source: &str) -> crate::report::Report {
+        crate::report::Report::build(&self.warnings, &self.policy, source)
+    }
+}
+
+/// Visits the expression(s) embedded directly in `stmt` — not the nested
+/// bodies of `If`/`While`/`For`, which the CFG already splits into their
+/// own blocks. Shared by every pass that scans a statement's own
+/// expressions without caring about its statement-level shape (loop
+/// variables, let-bindings, nested blocks, ...).
+fn for_each_stmt_expr<'e>(stmt: &'e Statement, mut visit: impl FnMut(&'e Expression)) {
+    match &stmt.kind {
+        StatementKind::Let { init, .. } => visit(init),
+        StatementKind::Expr(expr) => visit(expr),
+        StatementKind::Return(Some(expr)) => visit(expr),
+        StatementKind::If { condition, .. } => visit(condition),
+        StatementKind::While { condition, .. } => visit(condition),
+        StatementKind::For { start, end, .. } => {
+            visit(start);
+            visit(end);
+        }
+        _ => {}
+    }
+}
+
+/// Visits `expr`'s immediate child expressions, matching the recursion
+/// shape every pass over `ExpressionKind` in this module needs (both
+/// operands of a `Binary`, the callee and args of a `Call`, ...). Passes
+/// that need custom behavior at a particular node kind match on
+/// `expr.kind` themselves and fall back to this for the rest.
+fn for_each_child_expr<'e>(expr: &'e Expression, mut visit: impl FnMut(&'e Expression)) {
+    match &expr.kind {
+        ExpressionKind::Binary { left, right, .. } => {
+            visit(left);
+            visit(right);
+        }
+        ExpressionKind::Call { func, args, .. } => {
+            visit(func);
+            for arg in args {
+                visit(arg);
+            }
+        }
+        ExpressionKind::FieldAccess { expr: obj, .. } => visit(obj),
+        ExpressionKind::Index { expr: obj, index } => {
+            visit(obj);
+            visit(index);
+        }
+        ExpressionKind::Match { value, arms } => {
+            visit(value);
+            for arm in arms {
+                visit(&arm.body);
+            }
+        }
+        ExpressionKind::StructInit { fields, .. } => {
+            for (_, f_expr) in fields {
+                visit(f_expr);
+            }
+        }
+        ExpressionKind::Try(e) => visit(e),
+        ExpressionKind::GenericInst { target, .. } => visit(target),
+        _ => {}
+    }
+}
+
+/// If `expr` is `self.<field>`, returns `field`.
+fn self_field(expr: &Expression) -> Option<&str> {
+    let ExpressionKind::FieldAccess { expr: obj, field } = &expr.kind else {
+        return None;
+    };
+    let ExpressionKind::Identifier(name) = &obj.kind else {
+        return None;
+    };
+    (name == "self").then_some(field.as_str())
+}
+
+/// Whether `func` (a `Call`'s callee) looks like a call into another
+/// contract/object — `self.method(...)` and free-function calls aren't
+/// external, anything else shaped like `obj.method(...)` is.
+fn is_external_call_target(func: &Expression) -> bool {
+    let ExpressionKind::FieldAccess { expr: obj, .. } = &func.kind else {
+        return false;
+    };
+    !matches!(&obj.kind, ExpressionKind::Identifier(name) if name == "self")
+}
+
+/// Lines covered by a `// swiftsc-allow <code>` comment for `code`: the
+/// comment's own line (trailing suppression) and the line right after it
+/// (suppression attached to the next statement).
+fn suppressed_lines(source: &str, code: &str) -> HashSet<usize> {
+    let mut lines = HashSet::new();
+    for (i, line) in source.lines().enumerate() {
+        let Some(idx) = line.find("swiftsc-allow") else {
+            continue;
+        };
+        let suppressed_code = line[idx + "swiftsc-allow".len()..].split_whitespace().next();
+        if suppressed_code == Some(code) {
+            let lineno = i + 1;
+            lines.insert(lineno);
+            lines.insert(lineno + 1);
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn warnings_for(source: &str) -> Vec<SecurityWarning> {
+        let program = swiftsc_frontend::parse(source).expect("source should parse");
+        let mut analyzer = SecurityAnalyzer::new();
+        analyzer.analyze_program(&program);
+        analyzer.get_warnings().to_vec()
+    }
+
+    fn has_use_before_init(warnings: &[SecurityWarning]) -> bool {
+        warnings.iter().any(|w| matches!(w, SecurityWarning::UseBeforeInit { .. }))
+    }
+
+    /// A storage field set only by the constructor, a parameter, and a
+    /// `for` loop's induction variable must all be treated as
+    /// definitely-initialized everywhere they're read afterwards.
+    #[test]
+    fn post_constructor_reads_params_and_loop_vars_are_not_flagged() {
+        let source = r#"
+            contract Wallet {
+                storage {
+                    balance: u64,
+                }
+
+                init(starting: u64) {
+                    self.balance = starting;
+                }
+
+                fn transfer(amount: u64) {
+                    if self.balance < amount {
+                        return;
+                    }
+                    self.balance = self.balance - amount;
+                }
+
+                fn sum_to(n: u64) -> u64 {
+                    let total = 0;
+                    for i in 0..n {
+                        total = total + i;
+                    }
+                    return total;
+                }
+            }
+        "#;
+
+        let warnings = warnings_for(source);
+        assert!(
+            !has_use_before_init(&warnings),
+            "expected no spurious UseBeforeInit warnings, got {:?}",
+            warnings
+        );
+    }
+
+    fn has_reentrancy(warnings: &[SecurityWarning]) -> bool {
+        warnings.iter().any(|w| matches!(w, SecurityWarning::PotentialReentrancy { .. }))
+    }
+
+    /// A state write reachable only via the branch that made an external
+    /// call first is still flagged.
+    #[test]
+    fn state_write_after_call_on_reachable_branch_is_flagged() {
+        let source = r#"
+            contract Wallet {
+                storage {
+                    balance: u64,
+                }
+
+                init(starting: u64) {
+                    self.balance = starting;
+                }
+
+                fn withdraw(amount: u64) {
+                    if amount > 0 {
+                        external.call();
+                        self.balance = self.balance - amount;
+                    }
+                }
+            }
+        "#;
+
+        let warnings = warnings_for(source);
+        assert!(
+            has_reentrancy(&warnings),
+            "expected a PotentialReentrancy warning, got {:?}",
+            warnings
+        );
+    }
+
+    /// A branch that calls out and then returns must not taint the state
+    /// write on the sibling branch that never made the call — the `return`
+    /// ends that path there instead of falling through to the write.
+    #[test]
+    fn early_return_after_call_does_not_taint_other_branch() {
+        let source = r#"
+            contract Wallet {
+                storage {
+                    balance: u64,
+                }
+
+                init(starting: u64) {
+                    self.balance = starting;
+                }
+
+                fn withdraw(amount: u64, guard: bool) {
+                    if guard {
+                        external.call();
+                        return;
+                    }
+                    self.balance = self.balance - amount;
+                }
+            }
+        "#;
+
+        let warnings = warnings_for(source);
+        assert!(
+            !has_reentrancy(&warnings),
+            "expected no PotentialReentrancy warning, got {:?}",
+            warnings
+        );
+    }
+
+    /// A storage field the constructor never assigns is still flagged.
+    #[test]
+    fn constructor_missing_a_storage_assignment_is_flagged() {
+        let source = r#"
+            contract Wallet {
+                storage {
+                    balance: u64,
+                    owner: u64,
+                }
+
+                init(starting: u64) {
+                    self.balance = starting;
+                }
+            }
+        "#;
+
+        let warnings = warnings_for(source);
+        assert!(warnings
+            .iter()
+            .any(|w| matches!(w, SecurityWarning::UninitializedVariable { name, .. } if name.contains("owner"))));
+    }
+
+    /// A constructor branch that returns early without assigning every
+    /// storage field must still be flagged — the early return makes that
+    /// branch its own CFG exit, separate from the path that assigns
+    /// everything.
+    #[test]
+    fn constructor_early_return_branch_missing_a_field_is_flagged() {
+        let source = r#"
+            contract Wallet {
+                storage {
+                    mode: u64,
+                    extra: u64,
+                }
+
+                init(flag: bool) {
+                    if flag {
+                        self.mode = 1;
+                        return;
+                    }
+                    self.mode = 2;
+                    self.extra = 5;
+                }
+            }
+        "#;
+
+        let warnings = warnings_for(source);
+        assert!(warnings
+            .iter()
+            .any(|w| matches!(w, SecurityWarning::UninitializedVariable { name, .. } if name.contains("extra"))));
     }
 }